@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use linear_alg::{Vec3, Vec3A};
+
+fn bench_dot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dot");
+
+    let a = Vec3::new(1., 2., 3.);
+    let b = Vec3::new(4., 5., 6.);
+    group.bench_function("Vec3", |bencher| {
+        bencher.iter(|| black_box(a).dot(black_box(b)))
+    });
+
+    let a = Vec3A::new(1., 2., 3.);
+    let b = Vec3A::new(4., 5., 6.);
+    group.bench_function("Vec3A", |bencher| {
+        bencher.iter(|| black_box(a).dot(black_box(b)))
+    });
+
+    group.finish();
+}
+
+fn bench_normalize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("normalize");
+
+    let v = Vec3::new(1., 2., 3.);
+    group.bench_function("Vec3", |bencher| bencher.iter(|| black_box(v).normalize()));
+
+    let v = Vec3A::new(1., 2., 3.);
+    group.bench_function("Vec3A", |bencher| bencher.iter(|| black_box(v).normalize()));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_dot, bench_normalize);
+criterion_main!(benches);
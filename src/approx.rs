@@ -0,0 +1,80 @@
+//! Approximate equality for floating-point-ish types. Replaces the old
+//! Vec3::is_close, which baked in a fixed epsilon and only existed on Vec3.
+
+use crate::Vec3;
+
+/// Default tolerance used by [`ApproxEq::approx_eq`].
+pub const DEFAULT_EPSILON: f64 = 0.000001;
+
+pub trait ApproxEq {
+    /// Returns `true` if `self` and `other` differ by less than `eps`.
+    fn approx_eq_eps(&self, other: &Self, eps: f64) -> bool;
+
+    /// Returns `true` if `self` and `other` differ by less than
+    /// [`DEFAULT_EPSILON`].
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.approx_eq_eps(other, DEFAULT_EPSILON)
+    }
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq_eps(&self, other: &Self, eps: f64) -> bool {
+        (self - other).abs() < eps
+    }
+}
+
+impl ApproxEq for Vec3 {
+    // Compared component-wise rather than via vector length: two vectors
+    // whose difference has a tiny length can still differ a lot on one axis
+    // and cancel out on another, which length-based comparison would hide.
+    fn approx_eq_eps(&self, other: &Self, eps: f64) -> bool {
+        self.x.approx_eq_eps(&other.x, eps)
+            && self.y.approx_eq_eps(&other.y, eps)
+            && self.z.approx_eq_eps(&other.z, eps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{dot, rand_in_unit_cube};
+
+    #[test]
+    fn f64_approx_eq() {
+        assert!(1.0_f64.approx_eq(&1.0000001));
+        assert!(!1.0_f64.approx_eq(&1.1));
+        assert!(1.0_f64.approx_eq_eps(&1.05, 0.1));
+    }
+
+    #[test]
+    fn vec3_approx_eq_is_componentwise() {
+        // Differs only on `y`, by more than the default epsilon.
+        let a = Vec3::new(0, 0, 0);
+        let b = Vec3::new(0, 1, 0);
+
+        assert!(!a.approx_eq(&b));
+        assert!(a.approx_eq_eps(&b, 2.0));
+
+        // Every axis is within `eps`, although the difference vector is
+        // longer than `eps` (about 1.39 against 1.0): a length-based
+        // comparison would reject this pair.
+        let c = Vec3::new(0.8, 0.8, 0.8);
+        assert!(a.approx_eq_eps(&c, 1.0));
+    }
+
+    // Hold some basic vector identities over a large sample of random
+    // vectors.
+    #[test]
+    fn random_vector_identities() {
+        for _ in 0..10_000 {
+            let v = rand_in_unit_cube();
+            let w = rand_in_unit_cube();
+            let n = 3.7;
+
+            assert!((v * n / n).approx_eq(&v));
+            assert!(((v + w) - w).approx_eq(&v));
+            assert!(dot(v, w).approx_eq(&dot(w, v)));
+        }
+    }
+}
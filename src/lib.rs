@@ -1,196 +1,105 @@
 
 use rand::Rng;
-use std::{convert::Into, ops};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Vec3 {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
-}
-
-/* 
-//------------------------------//
-// Vector Comparaison functions //
-//------------------------------//
-
-impl cmp::PartialEq for Vec3 {
-    fn eq(&self, other: &Self) -> bool {
-        (self.x == other.x) && (self.y == other.y) && (self.z == other.z)
-    }
-}
- */
+pub mod approx;
+pub mod typed;
+pub mod vec3a;
+pub mod vector;
 
-//-----------------------------//
-// Vector Operations functions //
-//-----------------------------//
+pub use approx::ApproxEq;
+pub use typed::{TypedVec3, UnknownUnit, UntypedVec3};
+pub use vec3a::Vec3A;
+pub use vector::*;
 
-
-// vector-vector operator
-
-impl ops::Add<Vec3> for Vec3 {
-    type Output = Vec3;
-
-    fn add(self, rhs: Self) -> Vec3 {
-        Vec3::new(
-            self.x+rhs.x, 
-            self.y+rhs.y, 
-            self.z+rhs.z,
-                )
-    }
+///
+/// Returns the reflection of v according to Vect(n)
+/// An optimized version exist and doesn't normalize
+/// n at each call in order to perform less calculations
+///
+pub fn reflect(v: Vec3, n: Vec3) -> Vec3 {
+    let n = normalize(n);
+    n * 2. * dot(n, v) - v
 }
 
-impl ops::Sub<Vec3> for Vec3 {
-    type Output = Vec3;
-
-    fn sub(self, rhs: Self) -> Vec3 {
-        Vec3::new(
-            self.x-rhs.x, 
-            self.y-rhs.y, 
-            self.z-rhs.z,
-                )
-    }
+///
+/// 'n' MUST be normalized
+/// Returns the reflection of v according to Vect(n)
+///
+pub fn reflect_opt(v: Vec3, n: Vec3) -> Vec3 {
+    n * 2. * dot(n, v) - v
 }
 
-impl ops::Mul<Vec3> for Vec3 {
-    type Output = Vec3;
-
-    fn mul(self, rhs: Self) -> Vec3 {
-        Vec3::new(
-            self.x*rhs.x, 
-            self.y*rhs.y, 
-            self.z*rhs.z,
-                )
-    }
+///
+/// Returns the Snell's-law refraction of v through the surface of normal
+/// 'n', given 'eta_ratio' the ratio of the incident over the transmitted
+/// index of refraction. Returns 'None' on total internal reflection, in
+/// which case the caller should fall back to 'reflect'.
+///
+pub fn refract(v: Vec3, n: Vec3, eta_ratio: f64) -> Option<Vec3> {
+    refract_opt(v, normalize(n), eta_ratio)
 }
 
-impl ops::Div<Vec3> for Vec3 {
-    type Output = Vec3;
+///
+/// 'n' MUST be normalized
+/// Returns the Snell's-law refraction of v through the surface of normal
+/// 'n', given 'eta_ratio' the ratio of the incident over the transmitted
+/// index of refraction. Returns 'None' on total internal reflection, in
+/// which case the caller should fall back to 'reflect_opt'.
+///
+pub fn refract_opt(v: Vec3, n: Vec3, eta_ratio: f64) -> Option<Vec3> {
+    let uv = normalize(v);
+    let cos_theta = f64::min(dot(-uv, n), 1.0);
+    let sin_theta = f64::sqrt(1.0 - cos_theta * cos_theta);
 
-    fn div(self, rhs: Self) -> Vec3 {
-        assert_ne!(rhs.x*rhs.y*rhs.z, 0., "Error: Division by 0");
-        Vec3::new(
-            self.x/rhs.x, 
-            self.y/rhs.y, 
-            self.z/rhs.z,
-                )
+    if eta_ratio * sin_theta > 1.0 {
+        return None;
     }
-}
-
-// vector-Into(float) operator
 
-impl<T: Into<f64>> ops::Mul<T> for Vec3 {
-    type Output = Vec3;
+    let r_perp = (uv + n * cos_theta) * eta_ratio;
+    let r_parallel = n * -f64::sqrt(f64::abs(1.0 - length_sq(r_perp)));
 
-    fn mul(self, rhs: T) -> Vec3 {
-        let val = rhs.into();
-        Vec3::new(
-            self.x*val, 
-            self.y*val, 
-            self.z*val,
-                )
-    }
+    Some(r_perp + r_parallel)
 }
 
-
-impl<T: Into<f64>> ops::Div<T> for Vec3 {
-    type Output = Vec3;
-
-    fn div(self, rhs: T) -> Vec3 {
-        let val = rhs.into();
-        assert_ne!(val, 0., "Error : Division by 0");
-        Vec3::new(
-            self.x/val, 
-            self.y/val, 
-            self.z/val,
-                )
+pub fn rand_in_unit_cube() -> Vec3 {
+    let mut rng = rand::thread_rng();
+    Vec3 {
+        x: rng.gen_range::<f64, _>(-1.0..=1.0),
+        y: rng.gen_range::<f64, _>(-1.0..=1.0),
+        z: rng.gen_range::<f64, _>(-1.0..=1.0),
     }
 }
 
-// Vector struct Utility functions
-
-impl Vec3 {
-    
-    pub fn zero() -> Vec3 {
-        Vec3{
-            x: 0., 
-            y: 0., 
-            z: 0.,
-        }
-    }
-
-    pub fn new<X, Y, Z>(x: X, y: Y, z: Z) -> Vec3 
-        where X: Into<f64>,
-              Y: Into<f64>,
-              Z: Into<f64>,
-    {
-        Vec3 {
-            x: x.into(),
-            y: y.into(),
-            z: z.into(),
-        }
-    }
+///
+/// Builds a right-handed orthonormal basis `(t, b, n_hat)` around `n`
+/// (not necessarily normalized), with `n_hat = normalize(n)`.
+///
+/// Uses Frisvad's branchless method, with a fallback for the
+/// near-degenerate case where `n_hat` points almost straight down `-z`.
+///
+pub fn orthonormal_basis(n: Vec3) -> (Vec3, Vec3, Vec3) {
+    let n_hat = normalize(n);
 
-    pub fn is_close(self, v: Vec3) -> bool {
-        length(self-v).abs() < 0.000001
+    if n_hat.z < -0.9999999 {
+        return (Vec3::new(0., -1., 0.), Vec3::new(-1., 0., 0.), n_hat);
     }
 
-}
+    let a = 1. / (1. + n_hat.z);
+    let c = -n_hat.x * n_hat.y * a;
 
-// Vector Utility functions
-
-pub fn length(v: Vec3) -> f64 {
-    f64::sqrt((v.x*v.x)+(v.y*v.y)+(v.z*v.z))
-}
-
-pub fn length_sq(v: Vec3) -> f64 {
-    (v.x*v.x)+(v.y*v.y)+(v.z*v.z)
-}
+    let t = Vec3::new(1. - n_hat.x * n_hat.x * a, c, -n_hat.x);
+    let b = Vec3::new(c, 1. - n_hat.y * n_hat.y * a, -n_hat.y);
 
-pub fn normalize(v: Vec3) -> Vec3 {
-    v / length(v)
+    (t, b, n_hat)
 }
 
-pub fn dot(v1: Vec3, v2: Vec3) -> f64 {
-    v1.x*v2.x + v1.y*v2.y + v1.z*v2.z
-}
-
-/// 
-/// Returns the cross product between 'v1' and 'v2'
-/// 
-pub fn cross(v1: Vec3, v2: Vec3) -> Vec3 {
-    Vec3 {
-        x: v1.y*v2.z - v2.y*v1.z,
-        y: v1.z*v2.x - v2.z*v1.x,
-        z: v1.x*v2.y - v2.x*v1.y,
-    }
-}
-
-/// 
-/// Returns the reflection of v according to Vect(n)
-/// An optimized version exist and doesn't normalize 
-/// n at each call in order to perform less calculations
-/// 
-pub fn reflect(v: Vec3, n: Vec3) -> Vec3 {
-    let n = normalize(n);
-    n * 2.*dot(n, v) - v
-}
-
-///
-/// 'n' MUST be normalized 
-/// Returns the reflection of v according to Vect(n)
-///
-pub fn reflect_opt(v: Vec3, n: Vec3) -> Vec3 {
-    n * 2.*dot(n, v) - v
-}
-
-pub fn rand_in_unit_cube() -> Vec3 {
-    let mut rng = rand::thread_rng();
-    Vec3 {
-        x: rng.gen_range::<f64, _>(-1.0..=1.0),
-        y: rng.gen_range::<f64, _>(-1.0..=1.0),
-        z: rng.gen_range::<f64, _>(-1.0..=1.0),
-    }
+/// Returns the three world axes `(x, y, z)` as an orthonormal basis.
+pub fn canonical_basis() -> (Vec3, Vec3, Vec3) {
+    (
+        Vec3::new(1., 0., 0.),
+        Vec3::new(0., 1., 0.),
+        Vec3::new(0., 0., 1.),
+    )
 }
 
 pub fn rand_on_unit_sphere() -> Vec3 {
@@ -198,28 +107,19 @@ pub fn rand_on_unit_sphere() -> Vec3 {
         let a = rand_in_unit_cube();
         let l = length(a);
         if l < 1. {
-            return a/l;
+            return a / l;
         }
     }
 }
 
-pub fn lerp(v1: Vec3, v2: Vec3, x: f64) -> Vec3 {
-    Vec3::new(
-        v1.x*(1.-x)+v2.x*x,
-        v1.y*(1.-x)+v2.y*x,
-        v1.z*(1.-x)+v2.z*x,
-    )
-}
-
-
 #[cfg(test)]
 mod tests {
 
     use super::*;
 
 
-    /* 
-     *  Impl test Vector struct 
+    /*
+     *  Impl test Vector struct
      */
 
     #[test]
@@ -236,8 +136,8 @@ mod tests {
 
     }
 
-    /* 
-     *  Cmp test Vector struct 
+    /*
+     *  Cmp test Vector struct
      */
 
     #[test]
@@ -249,11 +149,11 @@ mod tests {
             z: -9.,
         };
         assert_eq!(any_vec, same_vec);
-        assert!(any_vec.is_close(same_vec));
+        assert!(any_vec.approx_eq(&same_vec));
     }
 
-    /* 
-     *  Operators test Vector struct 
+    /*
+     *  Operators test Vector struct
      */
 
     #[test]
@@ -333,9 +233,40 @@ mod tests {
         Vec3::new(4, -0.9, 100000) / 0;
     }
 
+    #[test]
+    fn op_assign() {
+        let mut a = Vec3::new(4, -0.5, 100000);
+        let b = Vec3::new(-4., 3, 9.);
+
+        a += b;
+        assert_eq!(a, Vec3::new(0, 2.5, 100009));
+
+        a -= b;
+        assert_eq!(a, Vec3::new(4, -0.5, 100000));
+
+        a *= 2;
+        assert_eq!(a, Vec3::new(8., -1., 200000));
 
-    /* 
-     *   Vector Utility functions test 
+        a /= 2;
+        assert_eq!(a, Vec3::new(4, -0.5, 100000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by 0")]
+    fn op_div_assign_scal_panic() {
+        let mut a = Vec3::new(4, -0.9, 100000);
+        a /= 0.;
+    }
+
+    #[test]
+    fn op_neg() {
+        let a = Vec3::new(4, -0.9, 100000);
+        assert_eq!(-a, Vec3::new(-4, 0.9, -100000));
+    }
+
+
+    /*
+     *   Vector Utility functions test
      */
 
 
@@ -378,7 +309,7 @@ mod tests {
             Vec3::new(0., 0., 1.)
         );
 
-        
+
         assert_eq!(
             cross(Vec3::new(0., 1., 0.), Vec3::new(0., 0., 1.)),
             Vec3::new(1., 0., 0.)
@@ -388,7 +319,7 @@ mod tests {
             cross(Vec3::new(0., 0., 1.), Vec3::new(1., 0., 0.)),
             Vec3::new(0., 1., 0.)
         );
-        
+
 
 
         assert_eq!(
@@ -401,7 +332,7 @@ mod tests {
             cross(Vec3::new(1., 2., 3.), Vec3::new(9., -6., 0.2)),
             Vec3::new(18.4, 26.8, -24.),
         );
-        
+
     }
 
 
@@ -419,7 +350,118 @@ mod tests {
         let v = Vec3::new(3, 3, 3);
         let n = Vec3::new(-4, -4, -4);
 
-        assert!(reflect(v, n).is_close(Vec3::new(3, 3, 3)));
+        assert!(reflect(v, n).approx_eq(&Vec3::new(3, 3, 3)));
+    }
+
+    #[test]
+    fn util_refract_and_refract_opt() {
+        // Straight-on incidence between equal indices: no bend, and no
+        // total internal reflection (`eta_ratio * sin_theta <= 1.0`,
+        // trivially true here since sin_theta is 0).
+        let v = Vec3::new(0, -1, 0);
+        let n = Vec3::new(0, 1, 0);
+
+        let refracted = refract(v, n, 1.0).unwrap();
+        assert!(refracted.approx_eq(&v));
+
+        // A grazing ray leaving a much denser medium undergoes total internal
+        // reflection, so refract must bail out with `None`.
+        let v = Vec3::new(10, -0.01, 0);
+        let n = Vec3::new(0, 1, 0);
+        assert_eq!(refract(v, n, 2.5), None);
+
+        assert_eq!(refract(v, n, 1.0), refract_opt(v, normalize(n), 1.0));
+    }
+
+    #[test]
+    fn util_orthonormal_basis() {
+        for n in [
+            Vec3::new(0, 1, 0),
+            Vec3::new(1, 1, 1),
+            Vec3::new(3, -2, 5),
+            Vec3::new(0, 0, -1),
+        ] {
+            let (t, b, n_hat) = orthonormal_basis(n);
+
+            assert!(dot(t, b).approx_eq(&0.));
+            assert!(dot(t, n_hat).approx_eq(&0.));
+            assert!(dot(b, n_hat).approx_eq(&0.));
+
+            assert!(length(t).approx_eq(&1.));
+            assert!(length(b).approx_eq(&1.));
+            assert!(length(n_hat).approx_eq(&1.));
+
+            assert!(cross(t, b).approx_eq(&n_hat));
+        }
+    }
+
+    #[test]
+    fn util_canonical_basis() {
+        let (x, y, z) = canonical_basis();
+
+        assert_eq!(x, Vec3::new(1, 0, 0));
+        assert_eq!(y, Vec3::new(0, 1, 0));
+        assert_eq!(z, Vec3::new(0, 0, 1));
+    }
+
+    /*
+     *  Generic Vector2/Vector3/Vector4 tests
+     */
+
+    #[test]
+    fn generic_vec3_integer_arithmetic() {
+        let a = Vec3i::new(1, 2, 3);
+        let b = Vec3i::new(4, 5, 6);
+
+        assert_eq!(a + b, Vec3i::new(5, 7, 9));
+        assert_eq!(a.dot(b), 32);
+        assert_eq!(a * 2, Vec3i::new(2, 4, 6));
+    }
+
+    #[test]
+    fn generic_vec2_vec4_splat_and_length() {
+        let v2 = Vec2f::splat(3.0);
+        assert_eq!(v2, vec2f(3.0, 3.0));
+        assert!((v2.length() - (18.0f64).sqrt()).abs() < 0.00001);
+
+        let v4 = vec4f(1.0, 0.0, 0.0, 0.0);
+        assert!((v4.length() - 1.0).abs() < 0.00001);
+    }
+
+    #[test]
+    fn vec3_index_and_index_mut() {
+        let mut v = Vec3::new(1., 2., 3.);
+
+        assert_eq!(v[0], 1.);
+        assert_eq!(v[1], 2.);
+        assert_eq!(v[2], 3.);
+
+        v[1] = 20.;
+        assert_eq!(v, Vec3::new(1., 20., 3.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn vec3_index_out_of_bounds_panics() {
+        let v = Vec3::new(1., 2., 3.);
+        let _ = v[3];
+    }
+
+    #[test]
+    fn vec3_iter_and_from_iter() {
+        let v = Vec3::new(1., 2., 3.);
+
+        let doubled: Vec<f64> = v.iter().map(|c| c * 2.).collect();
+        assert_eq!(doubled, vec![2., 4., 6.]);
+
+        let back: Vec3 = doubled.into_iter().collect();
+        assert_eq!(back, Vec3::new(2., 4., 6.));
+    }
+
+    #[test]
+    fn vec3_map() {
+        let v = Vec3::new(-1., 2., -3.);
+        assert_eq!(v.map(f64::abs), Vec3::new(1., 2., 3.));
     }
 
 }
@@ -0,0 +1,247 @@
+//! Typed units for vectors via a zero-sized unit marker. Mixing a position,
+//! a velocity and a surface normal as the same bare Vec3 is a common source
+//! of silent bugs; tagging a vector with a unit turns that mistake into a
+//! compile error instead, at zero runtime cost since the marker is a
+//! PhantomData.
+//!
+//! Vec3 itself stays `Vector3<f64>` rather than becoming an alias for
+//! `TypedVec3<f64, UnknownUnit>`: Vector3 already has indexing, iteration,
+//! FromIterator, etc., and re-deriving all of that on TypedVec3 just to
+//! unify the two wasn't worth it. `UntypedVec3<T>` plus the From conversions
+//! below bridge the two instead.
+
+use std::marker::PhantomData;
+use std::ops;
+
+use crate::vector::{VecFloat, VecScalar};
+
+/// Marker for "no particular unit" — the typed equivalent of the crate's
+/// plain, untyped vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownUnit;
+
+/// A 3D vector tagged with a unit `U`. Two `TypedVec3` carrying different
+/// units are different types, so e.g. `TypedVec3<f64, Meters> + TypedVec3<f64, Pixels>`
+/// simply doesn't compile.
+pub struct TypedVec3<T, U> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    unit: PhantomData<U>,
+}
+
+/// The untyped equivalent of [`TypedVec3`]: same representation as the
+/// crate's plain `Vec3`, with no unit guarantees.
+pub type UntypedVec3<T> = TypedVec3<T, UnknownUnit>;
+
+// Manual Clone/Copy/Debug/PartialEq impls: a `#[derive(...)]` here would add
+// a spurious `U: Trait` bound, even though `U` only ever appears inside a
+// `PhantomData` and never actually needs to implement anything.
+
+impl<T: Clone, U> Clone for TypedVec3<T, U> {
+    fn clone(&self) -> Self {
+        TypedVec3 {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: self.z.clone(),
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, U> Copy for TypedVec3<T, U> {}
+
+impl<T: std::fmt::Debug, U> std::fmt::Debug for TypedVec3<T, U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedVec3")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .finish()
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for TypedVec3<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y && self.z == other.z
+    }
+}
+
+impl<T: VecScalar, U> TypedVec3<T, U> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        TypedVec3 {
+            x,
+            y,
+            z,
+            unit: PhantomData,
+        }
+    }
+
+    pub fn splat(v: T) -> Self {
+        Self::new(v, v, v)
+    }
+
+    pub fn zero() -> Self {
+        Self::splat(T::default())
+    }
+
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    /// Returns the cross product between `self` and `rhs`.
+    pub fn cross(self, rhs: Self) -> Self {
+        TypedVec3::new(
+            self.y * rhs.z - rhs.y * self.z,
+            self.z * rhs.x - rhs.z * self.x,
+            self.x * rhs.y - rhs.x * self.y,
+        )
+    }
+
+    /// Deliberately reinterprets this vector as carrying the unit `V`
+    /// instead of `U`, without touching the components. Use this at the
+    /// (rare) boundary where mixing units is actually intended.
+    pub fn cast_unit<V>(self) -> TypedVec3<T, V> {
+        TypedVec3::new(self.x, self.y, self.z)
+    }
+}
+
+impl<T: VecFloat, U> TypedVec3<T, U> {
+    pub fn length_sq(self) -> f64 {
+        let x: f64 = self.x.into();
+        let y: f64 = self.y.into();
+        let z: f64 = self.z.into();
+        x * x + y * y + z * z
+    }
+
+    pub fn length(self) -> f64 {
+        self.length_sq().sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        self / T::from_f64(self.length())
+    }
+}
+
+impl<T: VecScalar, U> ops::Add<TypedVec3<T, U>> for TypedVec3<T, U> {
+    type Output = TypedVec3<T, U>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        TypedVec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: VecScalar, U> ops::Sub<TypedVec3<T, U>> for TypedVec3<T, U> {
+    type Output = TypedVec3<T, U>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        TypedVec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+// Scalar multiply/divide change the vector's magnitude but, unlike
+// vector-vector ops, don't mix two different units, so they stay available
+// for any `U`.
+
+impl<T: VecScalar, U> ops::Mul<T> for TypedVec3<T, U> {
+    type Output = TypedVec3<T, U>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        TypedVec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: VecScalar, U> ops::Div<T> for TypedVec3<T, U> {
+    type Output = TypedVec3<T, U>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        assert_ne!(rhs, T::default(), "Error : Division by 0");
+        TypedVec3::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl<T: VecScalar, U> ops::AddAssign<TypedVec3<T, U>> for TypedVec3<T, U> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x = self.x + rhs.x;
+        self.y = self.y + rhs.y;
+        self.z = self.z + rhs.z;
+    }
+}
+
+impl<T: VecScalar, U> ops::SubAssign<TypedVec3<T, U>> for TypedVec3<T, U> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x = self.x - rhs.x;
+        self.y = self.y - rhs.y;
+        self.z = self.z - rhs.z;
+    }
+}
+
+impl<T: VecScalar, U> ops::MulAssign<T> for TypedVec3<T, U> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x = self.x * rhs;
+        self.y = self.y * rhs;
+        self.z = self.z * rhs;
+    }
+}
+
+impl<T: VecScalar, U> ops::DivAssign<T> for TypedVec3<T, U> {
+    fn div_assign(&mut self, rhs: T) {
+        assert_ne!(rhs, T::default(), "Error : Division by 0");
+        self.x = self.x / rhs;
+        self.y = self.y / rhs;
+        self.z = self.z / rhs;
+    }
+}
+
+impl<T: VecScalar + ops::Neg<Output = T>, U> ops::Neg for TypedVec3<T, U> {
+    type Output = TypedVec3<T, U>;
+
+    fn neg(self) -> Self::Output {
+        TypedVec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: VecScalar> From<crate::Vector3<T>> for UntypedVec3<T> {
+    fn from(v: crate::Vector3<T>) -> Self {
+        TypedVec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: VecScalar> From<UntypedVec3<T>> for crate::Vector3<T> {
+    fn from(v: UntypedVec3<T>) -> Self {
+        crate::Vector3::new(v.x, v.y, v.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Vec3;
+
+    struct Meters;
+    struct Pixels;
+
+    #[test]
+    fn compile_time_unit_safety() {
+        let a = TypedVec3::<f64, Meters>::new(1., 2., 3.);
+        let b = TypedVec3::<f64, Meters>::new(4., 5., 6.);
+
+        assert_eq!(a + b, TypedVec3::new(5., 7., 9.));
+        assert_eq!(a * 2., TypedVec3::new(2., 4., 6.));
+
+        // `a + TypedVec3::<f64, Pixels>::new(...)` would not compile: that's
+        // the point, but it can't be expressed as a runtime test.
+        let as_pixels: TypedVec3<f64, Pixels> = a.cast_unit();
+        assert_eq!(as_pixels.x, a.x);
+    }
+
+    #[test]
+    fn untyped_round_trips_through_vec3() {
+        let v = Vec3::new(1., -2., 3.);
+        let typed: UntypedVec3<f64> = v.into();
+        let back: Vec3 = typed.into();
+
+        assert_eq!(back, v);
+    }
+}
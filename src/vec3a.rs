@@ -0,0 +1,277 @@
+//! Vec3A: a 16-byte-aligned, SIMD-backed f32 vector for hot inner loops
+//! (ray tracing, particle sims), falling back to plain scalar ops on
+//! unsupported targets.
+
+use std::ops;
+
+use crate::Vec3;
+
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+#[cfg(target_arch = "wasm32")]
+use core::arch::wasm32::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(align(16))]
+pub struct Vec3A {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vec3A {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Vec3A { x, y, z }
+    }
+
+    pub fn zero() -> Self {
+        Self::splat(0.)
+    }
+
+    pub fn splat(v: f32) -> Self {
+        Vec3A { x: v, y: v, z: v }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[inline]
+    fn to_lanes(self) -> __m128 {
+        unsafe { _mm_set_ps(0., self.z, self.y, self.x) }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[inline]
+    fn from_lanes(v: __m128) -> Self {
+        let mut lanes = [0f32; 4];
+        unsafe { _mm_storeu_ps(lanes.as_mut_ptr(), v) };
+        Vec3A::new(lanes[0], lanes[1], lanes[2])
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    fn to_lanes(self) -> v128 {
+        f32x4(self.x, self.y, self.z, 0.)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    fn from_lanes(v: v128) -> Self {
+        Vec3A::new(f32x4_extract_lane::<0>(v), f32x4_extract_lane::<1>(v), f32x4_extract_lane::<2>(v))
+    }
+
+    pub fn dot(self, rhs: Self) -> f32 {
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            let mul = _mm_mul_ps(self.to_lanes(), rhs.to_lanes());
+            let mut lanes = [0f32; 4];
+            _mm_storeu_ps(lanes.as_mut_ptr(), mul);
+            lanes[0] + lanes[1] + lanes[2]
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mul = f32x4_mul(self.to_lanes(), rhs.to_lanes());
+            f32x4_extract_lane::<0>(mul) + f32x4_extract_lane::<1>(mul) + f32x4_extract_lane::<2>(mul)
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+        {
+            self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+        }
+    }
+
+    /// Returns the cross product between `self` and `rhs`.
+    pub fn cross(self, rhs: Self) -> Self {
+        Vec3A {
+            x: self.y * rhs.z - rhs.y * self.z,
+            y: self.z * rhs.x - rhs.z * self.x,
+            z: self.x * rhs.y - rhs.x * self.y,
+        }
+    }
+
+    pub fn length_sq(self) -> f32 {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> f32 {
+        self.length_sq().sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        self / self.length()
+    }
+
+    ///
+    /// Returns the reflection of `self` according to `n`, normalizing `n`
+    /// first (see the crate-level `reflect`/`reflect_opt` for the non-SIMD split).
+    ///
+    pub fn reflect(self, n: Self) -> Self {
+        let n = n.normalize();
+        n * (2. * n.dot(self)) - self
+    }
+
+    ///
+    /// `n` MUST already be normalized.
+    ///
+    pub fn reflect_opt(self, n: Self) -> Self {
+        n * (2. * n.dot(self)) - self
+    }
+}
+
+impl ops::Add<Vec3A> for Vec3A {
+    type Output = Vec3A;
+
+    fn add(self, rhs: Self) -> Vec3A {
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            Vec3A::from_lanes(_mm_add_ps(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Vec3A::from_lanes(f32x4_add(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+        {
+            Vec3A::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+        }
+    }
+}
+
+impl ops::Sub<Vec3A> for Vec3A {
+    type Output = Vec3A;
+
+    fn sub(self, rhs: Self) -> Vec3A {
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            Vec3A::from_lanes(_mm_sub_ps(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Vec3A::from_lanes(f32x4_sub(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+        {
+            Vec3A::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+        }
+    }
+}
+
+impl ops::Mul<Vec3A> for Vec3A {
+    type Output = Vec3A;
+
+    fn mul(self, rhs: Self) -> Vec3A {
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            Vec3A::from_lanes(_mm_mul_ps(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Vec3A::from_lanes(f32x4_mul(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+        {
+            Vec3A::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+        }
+    }
+}
+
+impl ops::Div<Vec3A> for Vec3A {
+    type Output = Vec3A;
+
+    fn div(self, rhs: Self) -> Vec3A {
+        assert_ne!(rhs.x, 0., "Error: Division by 0");
+        assert_ne!(rhs.y, 0., "Error: Division by 0");
+        assert_ne!(rhs.z, 0., "Error: Division by 0");
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            Vec3A::from_lanes(_mm_div_ps(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Vec3A::from_lanes(f32x4_div(self.to_lanes(), rhs.to_lanes()))
+        }
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "wasm32")))]
+        {
+            Vec3A::new(self.x / rhs.x, self.y / rhs.y, self.z / rhs.z)
+        }
+    }
+}
+
+impl ops::Mul<f32> for Vec3A {
+    type Output = Vec3A;
+
+    fn mul(self, rhs: f32) -> Vec3A {
+        self * Vec3A::splat(rhs)
+    }
+}
+
+impl ops::Div<f32> for Vec3A {
+    type Output = Vec3A;
+
+    fn div(self, rhs: f32) -> Vec3A {
+        assert_ne!(rhs, 0., "Error : Division by 0");
+        self / Vec3A::splat(rhs)
+    }
+}
+
+impl ops::Neg for Vec3A {
+    type Output = Vec3A;
+
+    fn neg(self) -> Vec3A {
+        Vec3A::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl From<Vec3> for Vec3A {
+    fn from(v: Vec3) -> Self {
+        Vec3A::new(v.x as f32, v.y as f32, v.z as f32)
+    }
+}
+
+impl From<Vec3A> for Vec3 {
+    fn from(v: Vec3A) -> Self {
+        Vec3::new(v.x as f64, v.y as f64, v.z as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::ApproxEq;
+
+    #[test]
+    fn op_add_sub_mul_div() {
+        let a = Vec3A::new(1., 2., 3.);
+        let b = Vec3A::new(4., 5., 6.);
+
+        assert_eq!(a + b, Vec3A::new(5., 7., 9.));
+        assert_eq!(b - a, Vec3A::new(3., 3., 3.));
+        assert_eq!(a * b, Vec3A::new(4., 10., 18.));
+        assert_eq!(a * 2., Vec3A::new(2., 4., 6.));
+        assert_eq!(-a, Vec3A::new(-1., -2., -3.));
+    }
+
+    #[test]
+    fn util_dot_and_cross() {
+        let a = Vec3A::new(1., 0., 0.);
+        let b = Vec3A::new(0., 1., 0.);
+
+        assert_eq!(a.dot(b), 0.);
+        assert_eq!(a.cross(b), Vec3A::new(0., 0., 1.));
+    }
+
+    #[test]
+    fn util_length_and_normalize() {
+        let v = Vec3A::new(3., 0., 4.);
+        assert_eq!(v.length(), 5.);
+        assert!((v.normalize().length() - 1.).abs() < 0.0001);
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        let v = Vec3::new(1.5, -2.5, 3.5);
+        let a: Vec3A = v.into();
+        let back: Vec3 = a.into();
+
+        assert!(back.approx_eq(&v));
+    }
+}
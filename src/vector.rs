@@ -0,0 +1,540 @@
+//! Generic Vector2/Vector3/Vector4 types.
+//!
+//! Vec2/Vec3/Vec4 stay the f64 aliases used by the rest of the crate; these
+//! are generic so integer grids and f32 data don't round-trip through f64.
+
+use std::ops;
+
+//--------------------//
+// Scalar trait bounds //
+//--------------------//
+
+/// Minimal bound shared by every component type a vector can be built over.
+///
+/// This covers the arithmetic the operator impls below need; it is
+/// deliberately small so integer types (grid coordinates, pixel offsets)
+/// satisfy it just as well as `f32`/`f64`.
+pub trait VecScalar:
+    Copy
+    + Default
+    + PartialEq
+    + std::fmt::Debug
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+{
+}
+
+impl<T> VecScalar for T where
+    T: Copy
+        + Default
+        + PartialEq
+        + std::fmt::Debug
+        + ops::Add<Output = Self>
+        + ops::Sub<Output = Self>
+        + ops::Mul<Output = Self>
+        + ops::Div<Output = Self>
+{
+}
+
+/// Extra bound needed by anything involving a square root or an interpolation
+/// factor (`length`, `normalize`, `lerp`, ...): only meaningful for the float
+/// component types (`f32`, `f64`).
+pub trait VecFloat: VecScalar + Into<f64> {
+    fn from_f64(v: f64) -> Self;
+}
+
+impl VecFloat for f32 {
+    fn from_f64(v: f64) -> Self {
+        v as f32
+    }
+}
+
+impl VecFloat for f64 {
+    fn from_f64(v: f64) -> Self {
+        v
+    }
+}
+
+//---------//
+// Vector2 //
+//---------//
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vector2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: VecScalar> Vector2<T> {
+    pub fn new<X: Into<T>, Y: Into<T>>(x: X, y: Y) -> Self {
+        Vector2 {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    pub fn splat(v: T) -> Self {
+        Vector2 { x: v, y: v }
+    }
+
+    pub fn from_value(v: T) -> Self {
+        Self::splat(v)
+    }
+
+    pub fn zero() -> Self {
+        Self::splat(T::default())
+    }
+
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y
+    }
+}
+
+impl<T: VecFloat> Vector2<T> {
+    pub fn length_sq(self) -> f64 {
+        let x: f64 = self.x.into();
+        let y: f64 = self.y.into();
+        x * x + y * y
+    }
+
+    pub fn length(self) -> f64 {
+        self.length_sq().sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        self / T::from_f64(self.length())
+    }
+
+    pub fn lerp(self, rhs: Self, t: f64) -> Self {
+        let a = T::from_f64(1. - t);
+        let b = T::from_f64(t);
+        Vector2 {
+            x: self.x * a + rhs.x * b,
+            y: self.y * a + rhs.y * b,
+        }
+    }
+}
+
+//---------//
+// Vector3 //
+//---------//
+
+// `repr(C)` pins the field order so `x`, `y`, `z` sit contiguously in
+// memory in that order, which `as_slice`/`as_mut_slice` below rely on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct Vector3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: VecScalar> Vector3<T> {
+    pub fn new<X: Into<T>, Y: Into<T>, Z: Into<T>>(x: X, y: Y, z: Z) -> Self {
+        Vector3 {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+        }
+    }
+
+    pub fn splat(v: T) -> Self {
+        Vector3 { x: v, y: v, z: v }
+    }
+
+    pub fn from_value(v: T) -> Self {
+        Self::splat(v)
+    }
+
+    pub fn zero() -> Self {
+        Self::splat(T::default())
+    }
+
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    /// Returns the cross product between `self` and `rhs`.
+    pub fn cross(self, rhs: Self) -> Self {
+        Vector3 {
+            x: self.y * rhs.z - rhs.y * self.z,
+            y: self.z * rhs.x - rhs.z * self.x,
+            z: self.x * rhs.y - rhs.x * self.y,
+        }
+    }
+
+    /// Borrows the three components as a slice, in `x, y, z` order.
+    ///
+    /// Safe because `Vector3` is `#[repr(C)]` with three `T` fields and no
+    /// padding, so it has the exact layout of `[T; 3]`.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self as *const Self as *const T, 3) }
+    }
+
+    /// Mutable counterpart to [`Vector3::as_slice`].
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self as *mut Self as *mut T, 3) }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Applies `f` to each component, e.g. `v.map(f64::abs)`.
+    pub fn map<F: Fn(T) -> T>(self, f: F) -> Self {
+        Vector3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+}
+
+impl<T: VecFloat> Vector3<T> {
+    pub fn length_sq(self) -> f64 {
+        let x: f64 = self.x.into();
+        let y: f64 = self.y.into();
+        let z: f64 = self.z.into();
+        x * x + y * y + z * z
+    }
+
+    pub fn length(self) -> f64 {
+        self.length_sq().sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        self / T::from_f64(self.length())
+    }
+
+    pub fn lerp(self, rhs: Self, t: f64) -> Self {
+        let a = T::from_f64(1. - t);
+        let b = T::from_f64(t);
+        Vector3 {
+            x: self.x * a + rhs.x * b,
+            y: self.y * a + rhs.y * b,
+            z: self.z * a + rhs.z * b,
+        }
+    }
+}
+
+impl<T: VecScalar> ops::Index<usize> for Vector3<T> {
+    type Output = T;
+
+    /// Indexes by axis (`0` = x, `1` = y, `2` = z).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= 3`.
+    fn index(&self, i: usize) -> &T {
+        &self.as_slice()[i]
+    }
+}
+
+impl<T: VecScalar> ops::IndexMut<usize> for Vector3<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.as_mut_slice()[i]
+    }
+}
+
+impl<T: VecScalar> FromIterator<T> for Vector3<T> {
+    /// Builds a `Vector3` from the first three items yielded by `iter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iter` yields fewer than three items.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        Vector3 {
+            x: iter.next().expect("Vector3::from_iter: missing x component"),
+            y: iter.next().expect("Vector3::from_iter: missing y component"),
+            z: iter.next().expect("Vector3::from_iter: missing z component"),
+        }
+    }
+}
+
+impl<'a, T: VecScalar> IntoIterator for &'a Vector3<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//---------//
+// Vector4 //
+//---------//
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vector4<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+}
+
+impl<T: VecScalar> Vector4<T> {
+    pub fn new<X: Into<T>, Y: Into<T>, Z: Into<T>, W: Into<T>>(x: X, y: Y, z: Z, w: W) -> Self {
+        Vector4 {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+            w: w.into(),
+        }
+    }
+
+    pub fn splat(v: T) -> Self {
+        Vector4 {
+            x: v,
+            y: v,
+            z: v,
+            w: v,
+        }
+    }
+
+    pub fn from_value(v: T) -> Self {
+        Self::splat(v)
+    }
+
+    pub fn zero() -> Self {
+        Self::splat(T::default())
+    }
+
+    pub fn dot(self, rhs: Self) -> T {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+}
+
+impl<T: VecFloat> Vector4<T> {
+    pub fn length_sq(self) -> f64 {
+        let x: f64 = self.x.into();
+        let y: f64 = self.y.into();
+        let z: f64 = self.z.into();
+        let w: f64 = self.w.into();
+        x * x + y * y + z * z + w * w
+    }
+
+    pub fn length(self) -> f64 {
+        self.length_sq().sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        self / T::from_f64(self.length())
+    }
+
+    pub fn lerp(self, rhs: Self, t: f64) -> Self {
+        let a = T::from_f64(1. - t);
+        let b = T::from_f64(t);
+        Vector4 {
+            x: self.x * a + rhs.x * b,
+            y: self.y * a + rhs.y * b,
+            z: self.z * a + rhs.z * b,
+            w: self.w * a + rhs.w * b,
+        }
+    }
+}
+
+//-----------------------------//
+// Vector Operations operators //
+//-----------------------------//
+
+macro_rules! impl_vector_ops {
+    ($Vector:ident { $($field:ident),+ }) => {
+        impl<T: VecScalar> ops::Add<$Vector<T>> for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $Vector { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: VecScalar> ops::Sub<$Vector<T>> for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $Vector { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: VecScalar> ops::Mul<$Vector<T>> for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                $Vector { $($field: self.$field * rhs.$field),+ }
+            }
+        }
+
+        impl<T: VecScalar> ops::Div<$Vector<T>> for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                $(assert_ne!(rhs.$field, T::default(), "Error: Division by 0");)+
+                $Vector { $($field: self.$field / rhs.$field),+ }
+            }
+        }
+
+        // vector-scalar operator
+        //
+        // Bounded by `S: VecScalar + Into<T>` rather than plain `S: Into<T>`
+        // so e.g. `Vec3::new(..) * 2` keeps working (integer literals default
+        // to `i32`, which is `Into<f64>`) without reintroducing the E0119
+        // conflict a plain `Into<T>` bound has with the vector-vector `Mul`
+        // above: `Vector3<T>` doesn't implement `VecScalar` (no `Default`),
+        // so it can never unify as `S` here.
+
+        impl<T: VecScalar, S: VecScalar + Into<T>> ops::Mul<S> for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn mul(self, rhs: S) -> Self::Output {
+                let rhs: T = rhs.into();
+                $Vector { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: VecScalar, S: VecScalar + Into<T>> ops::Div<S> for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn div(self, rhs: S) -> Self::Output {
+                let rhs: T = rhs.into();
+                assert_ne!(rhs, T::default(), "Error : Division by 0");
+                $Vector { $($field: self.$field / rhs),+ }
+            }
+        }
+
+        // in-place assignment operators
+
+        impl<T: VecScalar> ops::AddAssign<$Vector<T>> for $Vector<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field = self.$field + rhs.$field;)+
+            }
+        }
+
+        impl<T: VecScalar> ops::SubAssign<$Vector<T>> for $Vector<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field = self.$field - rhs.$field;)+
+            }
+        }
+
+        impl<T: VecScalar> ops::MulAssign<$Vector<T>> for $Vector<T> {
+            fn mul_assign(&mut self, rhs: Self) {
+                $(self.$field = self.$field * rhs.$field;)+
+            }
+        }
+
+        impl<T: VecScalar> ops::DivAssign<$Vector<T>> for $Vector<T> {
+            fn div_assign(&mut self, rhs: Self) {
+                $(assert_ne!(rhs.$field, T::default(), "Error: Division by 0");)+
+                $(self.$field = self.$field / rhs.$field;)+
+            }
+        }
+
+        impl<T: VecScalar, S: VecScalar + Into<T>> ops::MulAssign<S> for $Vector<T> {
+            fn mul_assign(&mut self, rhs: S) {
+                let rhs: T = rhs.into();
+                $(self.$field = self.$field * rhs;)+
+            }
+        }
+
+        impl<T: VecScalar, S: VecScalar + Into<T>> ops::DivAssign<S> for $Vector<T> {
+            fn div_assign(&mut self, rhs: S) {
+                let rhs: T = rhs.into();
+                assert_ne!(rhs, T::default(), "Error : Division by 0");
+                $(self.$field = self.$field / rhs;)+
+            }
+        }
+
+        // `Neg` is only available for component types that support negation
+        // (so unsigned integer vectors simply don't get it).
+
+        impl<T: VecScalar + ops::Neg<Output = T>> ops::Neg for $Vector<T> {
+            type Output = $Vector<T>;
+
+            fn neg(self) -> Self::Output {
+                $Vector { $($field: -self.$field),+ }
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Vector2 { x, y });
+impl_vector_ops!(Vector3 { x, y, z });
+impl_vector_ops!(Vector4 { x, y, z, w });
+
+//----------------------------//
+// f64-alias free functions   //
+//----------------------------//
+//
+// The rest of the crate (and its tests) predates the generic vectors above
+// and calls these as free functions on `Vec3`/`Vec2`/`Vec4`. Keep them
+// around as thin forwarders to the generic methods so those call sites keep
+// compiling unchanged.
+
+pub fn length<T: VecFloat>(v: Vector3<T>) -> f64 {
+    v.length()
+}
+
+pub fn length_sq<T: VecFloat>(v: Vector3<T>) -> f64 {
+    v.length_sq()
+}
+
+pub fn normalize<T: VecFloat>(v: Vector3<T>) -> Vector3<T> {
+    v.normalize()
+}
+
+pub fn dot<T: VecScalar>(v1: Vector3<T>, v2: Vector3<T>) -> T {
+    v1.dot(v2)
+}
+
+pub fn cross<T: VecScalar>(v1: Vector3<T>, v2: Vector3<T>) -> Vector3<T> {
+    v1.cross(v2)
+}
+
+pub fn lerp<T: VecFloat>(v1: Vector3<T>, v2: Vector3<T>, x: f64) -> Vector3<T> {
+    v1.lerp(v2, x)
+}
+
+//---------------------------//
+// Customary type aliases    //
+//---------------------------//
+
+pub type Vec2 = Vector2<f64>;
+pub type Vec3 = Vector3<f64>;
+pub type Vec4 = Vector4<f64>;
+
+pub type Vec2f = Vector2<f32>;
+pub type Vec3f = Vector3<f32>;
+pub type Vec4f = Vector4<f32>;
+
+pub type Vec2i = Vector2<i32>;
+pub type Vec3i = Vector3<i32>;
+pub type Vec4i = Vector4<i32>;
+
+pub fn vec2f(x: f32, y: f32) -> Vec2f {
+    Vector2::new(x, y)
+}
+
+pub fn vec2i(x: i32, y: i32) -> Vec2i {
+    Vector2::new(x, y)
+}
+
+pub fn vec3f(x: f32, y: f32, z: f32) -> Vec3f {
+    Vector3::new(x, y, z)
+}
+
+pub fn vec3i(x: i32, y: i32, z: i32) -> Vec3i {
+    Vector3::new(x, y, z)
+}
+
+pub fn vec4f(x: f32, y: f32, z: f32, w: f32) -> Vec4f {
+    Vector4::new(x, y, z, w)
+}
+
+pub fn vec4i(x: i32, y: i32, z: i32, w: i32) -> Vec4i {
+    Vector4::new(x, y, z, w)
+}